@@ -1,6 +1,14 @@
+mod monitor;
+mod overlay;
+
+pub use monitor::{
+    enumerate_monitors, monitor_from_point, primary_monitor, MonitorInfo, OverlayTarget,
+};
+pub use overlay::{Color, DrawAnchor, DrawItem, OverlayEvent, TextStyle, Window};
+
 use std::{
     ops::Deref,
-    sync::{Arc, RwLock},
+    sync::{mpsc::Sender, Arc, RwLock},
     time::{Duration, Instant},
 };
 use windows::{
@@ -9,21 +17,31 @@ use windows::{
         Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM},
         Graphics::Gdi::{
             CreatePen, CreateSolidBrush, FillRect, GetBkColor, GetDC, LineTo, MoveToEx, Rectangle,
-            SelectObject, HBRUSH, HDC, PS_SOLID,
+            SelectObject, StretchDIBits, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
+            HBRUSH, HDC, PS_SOLID, SRCCOPY,
         },
+        UI::Input::KeyboardAndMouse::{MOD_ALT, MOD_CONTROL, VK_F10, VK_F9},
         UI::WindowsAndMessaging::{
-            CreateWindowExA, DefWindowProcA, DispatchMessageA, GetMessageA, RegisterClassA,
-            SetLayeredWindowAttributes, TranslateMessage, CS_HREDRAW, CS_VREDRAW, LWA_COLORKEY,
-            MSG, WNDCLASSA, WS_EX_LAYERED, WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_POPUP, WS_VISIBLE,
+            CreateWindowExA, DefWindowProcA, DispatchMessageA, GetMessageA, GetWindowLongPtrA,
+            IsWindowVisible, PostQuitMessage, RegisterClassA, RegisterHotKey,
+            SetLayeredWindowAttributes, SetWindowLongPtrA, ShowWindow, TranslateMessage,
+            UnregisterHotKey, CREATESTRUCTA, CS_HREDRAW, CS_VREDRAW, GWLP_USERDATA, LWA_COLORKEY,
+            MSG, SW_HIDE, SW_SHOW, WM_DESTROY, WM_HOTKEY, WM_NCCREATE, WNDCLASSA, WS_EX_LAYERED,
+            WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_POPUP, WS_VISIBLE,
         },
     },
 };
 
+/// IDs passed to `RegisterHotKey`/`UnregisterHotKey` and echoed back in `WM_HOTKEY`'s `wparam`.
+const HOTKEY_ID_TOGGLE_VISIBILITY: i32 = 1;
+const HOTKEY_ID_QUIT: i32 = 2;
+
 #[derive(Debug)]
 pub enum OverlayError {
     RegisterClassA,
     CreateWindowExA,
     SetLayeredWindowAttributes,
+    StretchDIBits,
 }
 
 impl std::fmt::Display for OverlayError {
@@ -34,7 +52,7 @@ impl std::fmt::Display for OverlayError {
 
 impl std::error::Error for OverlayError {}
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 struct HDCWrapper(HDC);
 
 impl Deref for HDCWrapper {
@@ -48,36 +66,45 @@ impl Deref for HDCWrapper {
 unsafe impl Send for HDCWrapper {}
 unsafe impl Sync for HDCWrapper {}
 
+/// An ARGB pixel buffer (icon, mini health bar, heat map, ...) blitted onto the overlay each
+/// frame alongside the rect list.
+#[derive(Debug, Clone)]
+pub struct ImageItem {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub pixels: Vec<u8>,
+}
+
 #[derive(Debug)]
 pub struct Overlay {
     pub window_rect: RECT,
     pub draw_rect_list: Arc<RwLock<Vec<RECT>>>,
+    pub draw_image_list: Arc<RwLock<Vec<ImageItem>>>,
     pub pen_width: i32,
     pub frame_rate: u64,
     pub draw_bottom_line_flag: bool,
+    event_sender: Option<Sender<OverlayEvent>>,
 }
 
 impl Overlay {
     pub fn new(
-        left: i32,
-        top: i32,
-        right: i32,
-        bottom: i32,
+        target: OverlayTarget,
         draw_rect_list: Arc<RwLock<Vec<RECT>>>,
+        draw_image_list: Arc<RwLock<Vec<ImageItem>>>,
         frame_rate: u64,
         draw_bottom_line_flag: bool,
+        event_sender: Option<Sender<OverlayEvent>>,
     ) -> Self {
         Overlay {
-            window_rect: RECT {
-                left,
-                top,
-                right,
-                bottom,
-            },
+            window_rect: target.resolve(),
             draw_rect_list,
+            draw_image_list,
             pen_width: 1,
             frame_rate,
             draw_bottom_line_flag,
+            event_sender,
         }
     }
 
@@ -111,7 +138,7 @@ impl Overlay {
                 None,
                 None,
                 None,
-                None,
+                Some(self as *mut _ as _),
             )
             .map_err(|_| OverlayError::CreateWindowExA)?;
             let hdc = GetDC(window);
@@ -122,7 +149,21 @@ impl Overlay {
             let pen = CreatePen(PS_SOLID, self.pen_width, COLORREF(0xFF));
             SelectObject(hdc, pen);
 
+            let _ = RegisterHotKey(
+                Some(window),
+                HOTKEY_ID_TOGGLE_VISIBILITY,
+                MOD_CONTROL | MOD_ALT,
+                VK_F9.0 as u32,
+            );
+            let _ = RegisterHotKey(
+                Some(window),
+                HOTKEY_ID_QUIT,
+                MOD_CONTROL | MOD_ALT,
+                VK_F10.0 as u32,
+            );
+
             let draw_rect_list = self.draw_rect_list.clone();
+            let draw_image_list = self.draw_image_list.clone();
             let refresh_rect = RECT {
                 left: 0,
                 top: 0,
@@ -130,6 +171,8 @@ impl Overlay {
                 bottom: window_height,
             };
             let draw_bottom_line_flag = self.draw_bottom_line_flag;
+            let origin_x = self.window_rect.left;
+            let origin_y = self.window_rect.top;
             let hdc = HDCWrapper(hdc);
             let tick_rate = Duration::from_millis(1000 / self.frame_rate);
             std::thread::spawn(move || {
@@ -143,16 +186,36 @@ impl Overlay {
                         draw_rect_list_lock.clone()
                     };
                     draw_rect_list.iter().for_each(|rect| {
-                        let _ = Rectangle(*hdc, rect.left, rect.top, rect.right, rect.bottom);
+                        let left = rect.left - origin_x;
+                        let top = rect.top - origin_y;
+                        let right = rect.right - origin_x;
+                        let bottom = rect.bottom - origin_y;
+
+                        let _ = Rectangle(*hdc, left, top, right, bottom);
 
                         if draw_bottom_line_flag {
                             let _ =
                                 MoveToEx(*hdc, refresh_rect.right / 2, refresh_rect.bottom, None);
-                            let rect_width = rect.right - rect.left;
-                            let _ = LineTo(*hdc, rect.left + rect_width / 2, rect.bottom);
+                            let rect_width = right - left;
+                            let _ = LineTo(*hdc, left + rect_width / 2, bottom);
                         }
                     });
 
+                    let draw_image_list = {
+                        let draw_image_list_lock = draw_image_list.read().unwrap();
+                        draw_image_list_lock.clone()
+                    };
+                    draw_image_list.iter().for_each(|item| {
+                        let _ = blit_pixels(
+                            *hdc,
+                            item.x - origin_x,
+                            item.y - origin_y,
+                            item.width,
+                            item.height,
+                            &item.pixels,
+                        );
+                    });
+
                     let timeout = tick_rate.saturating_sub(last_tick.elapsed());
                     std::thread::sleep(timeout);
                     last_tick = Instant::now();
@@ -170,7 +233,104 @@ impl Overlay {
 }
 
 extern "system" fn wndproc(window: HWND, message: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
-    unsafe { DefWindowProcA(window, message, wparam, lparam) }
+    unsafe {
+        if message == WM_NCCREATE {
+            let cs = lparam.0 as *const CREATESTRUCTA;
+            let this = (*cs).lpCreateParams as *mut Overlay;
+            if !this.is_null() {
+                SetWindowLongPtrA(window, GWLP_USERDATA, this as _);
+            }
+            return DefWindowProcA(window, message, wparam, lparam);
+        }
+
+        match message {
+            WM_HOTKEY => {
+                let this = GetWindowLongPtrA(window, GWLP_USERDATA) as *mut Overlay;
+                match wparam.0 as i32 {
+                    HOTKEY_ID_TOGGLE_VISIBILITY => {
+                        let now_visible = !IsWindowVisible(window).as_bool();
+                        let _ = ShowWindow(window, if now_visible { SW_SHOW } else { SW_HIDE });
+                        if !this.is_null() {
+                            if let Some(sender) = &(*this).event_sender {
+                                let _ = sender.send(OverlayEvent::VisibilityToggled(now_visible));
+                            }
+                        }
+                    }
+                    HOTKEY_ID_QUIT => {
+                        if !this.is_null() {
+                            if let Some(sender) = &(*this).event_sender {
+                                let _ = sender.send(OverlayEvent::Quit);
+                            }
+                        }
+                        PostQuitMessage(0);
+                    }
+                    _ => {}
+                }
+                LRESULT(0)
+            }
+            WM_DESTROY => {
+                let _ = UnregisterHotKey(Some(window), HOTKEY_ID_TOGGLE_VISIBILITY);
+                let _ = UnregisterHotKey(Some(window), HOTKEY_ID_QUIT);
+                PostQuitMessage(0);
+                LRESULT(0)
+            }
+            _ => DefWindowProcA(window, message, wparam, lparam),
+        }
+    }
+}
+
+/// Blits a top-down 32bpp ARGB pixel buffer onto `hdc` via `StretchDIBits`.
+fn blit_pixels(
+    hdc: HDC,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    pixels: &[u8],
+) -> Result<(), OverlayError> {
+    let required_len = (width as i64)
+        .saturating_mul(height as i64)
+        .saturating_mul(4);
+    if width <= 0 || height <= 0 || (pixels.len() as i64) < required_len {
+        return Err(OverlayError::StretchDIBits);
+    }
+
+    let bitmap_info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0 as u32,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let lines_copied = unsafe {
+        StretchDIBits(
+            hdc,
+            x,
+            y,
+            width,
+            height,
+            0,
+            0,
+            width,
+            height,
+            Some(pixels.as_ptr() as *const _),
+            &bitmap_info,
+            DIB_RGB_COLORS,
+            SRCCOPY,
+        )
+    };
+
+    if lines_copied == 0 {
+        Err(OverlayError::StretchDIBits)
+    } else {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -200,7 +360,14 @@ mod tests {
 
         {
             let rect_list = rect_list.clone();
-            let mut overlay = Overlay::new(0, 0, 1920, 1080, rect_list, FRAME_RATE, true);
+            let target = OverlayTarget::Rect(RECT {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            });
+            let image_list = Arc::new(RwLock::new(Vec::new()));
+            let mut overlay = Overlay::new(target, rect_list, image_list, FRAME_RATE, true, None);
             std::thread::spawn(move || {
                 overlay.window_loop().unwrap();
             });
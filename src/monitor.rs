@@ -0,0 +1,133 @@
+use std::ffi::OsString;
+use std::os::windows::ffi::OsStringExt;
+
+use windows::Win32::Foundation::{BOOL, LPARAM, POINT, RECT};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, MonitorFromPoint, HDC, HMONITOR, MONITORINFOEXW,
+    MONITORINFOF_PRIMARY, MONITOR_DEFAULTTONEAREST,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+    SM_YVIRTUALSCREEN,
+};
+
+/// One display as reported by `EnumDisplayMonitors`/`GetMonitorInfoW`.
+#[derive(Debug, Clone)]
+pub struct MonitorInfo {
+    pub rect: RECT,
+    pub device_name: String,
+    pub is_primary: bool,
+}
+
+/// Where an overlay window should be placed: a caller-supplied rect, a single monitor, or the
+/// whole virtual desktop spanning every monitor.
+#[derive(Debug, Clone)]
+pub enum OverlayTarget {
+    Rect(RECT),
+    Monitor(MonitorInfo),
+    VirtualDesktop,
+}
+
+impl OverlayTarget {
+    pub fn resolve(self) -> RECT {
+        match self {
+            OverlayTarget::Rect(rect) => rect,
+            OverlayTarget::Monitor(monitor) => monitor.rect,
+            OverlayTarget::VirtualDesktop => virtual_desktop_rect(),
+        }
+    }
+}
+
+/// Enumerates every connected monitor via `EnumDisplayMonitors`.
+pub fn enumerate_monitors() -> Vec<MonitorInfo> {
+    let mut monitors: Vec<MonitorInfo> = Vec::new();
+
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            None,
+            None,
+            Some(enum_monitor_proc),
+            LPARAM(&mut monitors as *mut Vec<MonitorInfo> as isize),
+        );
+    }
+
+    monitors
+}
+
+extern "system" fn enum_monitor_proc(
+    monitor: HMONITOR,
+    _hdc: HDC,
+    _rect: *mut RECT,
+    lparam: LPARAM,
+) -> BOOL {
+    unsafe {
+        let monitors = &mut *(lparam.0 as *mut Vec<MonitorInfo>);
+
+        if let Some(info) = monitor_info(monitor) {
+            monitors.push(info);
+        }
+    }
+
+    true.into()
+}
+
+/// Returns the monitor the system's primary display sits on.
+pub fn primary_monitor() -> Option<MonitorInfo> {
+    enumerate_monitors().into_iter().find(|monitor| monitor.is_primary)
+}
+
+/// Returns the monitor nearest to a screen-space point, following `MonitorFromPoint`.
+pub fn monitor_from_point(x: i32, y: i32) -> Option<MonitorInfo> {
+    unsafe {
+        let monitor = MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONEAREST);
+        monitor_info(monitor)
+    }
+}
+
+fn monitor_info(monitor: HMONITOR) -> Option<MonitorInfo> {
+    unsafe {
+        let mut info = MONITORINFOEXW {
+            monitorInfo: windows::Win32::Graphics::Gdi::MONITORINFO {
+                cbSize: std::mem::size_of::<MONITORINFOEXW>() as u32,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        if !GetMonitorInfoW(monitor, &mut info as *mut _ as *mut _).as_bool() {
+            return None;
+        }
+
+        let name_len = info
+            .szDevice
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(info.szDevice.len());
+        let device_name = OsString::from_wide(&info.szDevice[..name_len])
+            .to_string_lossy()
+            .into_owned();
+
+        Some(MonitorInfo {
+            rect: info.monitorInfo.rcMonitor,
+            device_name,
+            is_primary: (info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY) != 0,
+        })
+    }
+}
+
+/// Computes the bounding rect of the whole virtual desktop (every monitor combined).
+pub fn virtual_desktop_rect() -> RECT {
+    unsafe {
+        let left = GetSystemMetrics(SM_XVIRTUALSCREEN);
+        let top = GetSystemMetrics(SM_YVIRTUALSCREEN);
+        let width = GetSystemMetrics(SM_CXVIRTUALSCREEN);
+        let height = GetSystemMetrics(SM_CYVIRTUALSCREEN);
+
+        RECT {
+            left,
+            top,
+            right: left + width,
+            bottom: top + height,
+        }
+    }
+}
@@ -1,54 +1,140 @@
-use std::sync::{Arc, RwLock};
+use std::collections::HashMap;
+use std::sync::{
+    mpsc::Sender,
+    {Arc, RwLock},
+};
 use windows::{
     core::*, Win32::Foundation::*, Win32::Graphics::Direct2D::Common::*,
     Win32::Graphics::Direct2D::*, Win32::Graphics::Direct3D::*, Win32::Graphics::Direct3D11::*,
-    Win32::Graphics::Dxgi::Common::*, Win32::Graphics::Dxgi::*, Win32::Graphics::Gdi::*,
+    Win32::Graphics::DirectWrite::*, Win32::Graphics::Dxgi::Common::*, Win32::Graphics::Dxgi::*,
+    Win32::Graphics::Gdi::*,
+    Win32::UI::Input::KeyboardAndMouse::{MOD_ALT, MOD_CONTROL, VK_F10, VK_F9},
     Win32::UI::WindowsAndMessaging::*,
 };
 
+use crate::monitor::OverlayTarget;
+
+/// IDs passed to `RegisterHotKey`/`UnregisterHotKey` and echoed back in `WM_HOTKEY`'s `wparam`.
+const HOTKEY_ID_TOGGLE_VISIBILITY: i32 = 1;
+const HOTKEY_ID_QUIT: i32 = 2;
+
+/// Runtime events a [`Window`] can report back to its caller over the event channel passed to
+/// [`Window::new`].
+#[derive(Debug, Clone)]
+pub enum OverlayEvent {
+    VisibilityToggled(bool),
+    Quit,
+}
+
+/// Where a [`DrawItem`]'s label is anchored relative to its box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawAnchor {
+    /// Label sits inside the box, flush with its top-left corner.
+    TopLeft,
+    /// Label sits just above the box.
+    AboveBox,
+}
+
+/// An RGBA color, used to key the brush cache on [`Window`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+/// A single box in the shared draw list, with an optional text label.
+#[derive(Debug, Clone)]
+pub struct DrawItem {
+    pub rect: RECT,
+    pub label: Option<String>,
+    pub anchor: DrawAnchor,
+    pub color: Color,
+    pub stroke_width: f32,
+    pub filled: bool,
+}
+
+/// Minimum stroke width the region bands in [`Window::update_window_region`] are sized for; an
+/// item with a wider `stroke_width` gets bands sized for its own stroke.
+const STROKE_WIDTH: i32 = 2;
+
+/// Height of a [`DrawItem`] label's layout rect, shared between [`Window::draw`] and
+/// [`Window::update_window_region`] so the clipped region always covers what's actually drawn.
+const LABEL_HEIGHT: i32 = 18;
+
+/// Upper bound on `Window::brush_cache`. Callers are expected to draw from a small, mostly
+/// fixed set of colors (team colors, a handful of health-bar stops, ...); if a caller instead
+/// varies color continuously (e.g. a per-frame health gradient) the cache is dropped and
+/// rebuilt rather than growing without bound for the process lifetime.
+const MAX_CACHED_BRUSHES: usize = 64;
+
+/// Font family, size and weight used to render [`DrawItem`] labels, passed to [`Window::new`].
+#[derive(Debug, Clone)]
+pub struct TextStyle {
+    pub font_family: String,
+    pub font_size: f32,
+    pub font_weight: DWRITE_FONT_WEIGHT,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        TextStyle {
+            font_family: "Segoe UI".to_string(),
+            font_size: 14.0,
+            font_weight: DWRITE_FONT_WEIGHT_NORMAL,
+        }
+    }
+}
+
 pub struct Window {
     handle: HWND,
 
     window_rect: RECT,
-    draw_rect_list: Arc<RwLock<Vec<RECT>>>,
+    draw_item_list: Arc<RwLock<Vec<DrawItem>>>,
     draw_bottom_line_flag: bool,
+    shape_window_region: bool,
+    region_hash: Option<u64>,
+    event_sender: Option<Sender<OverlayEvent>>,
 
     factory: ID2D1Factory1,
     style: ID2D1StrokeStyle1,
+    text_format: IDWriteTextFormat,
 
     target: Option<ID2D1DeviceContext>,
     swapchain: Option<IDXGISwapChain1>,
-    brush: Option<ID2D1SolidColorBrush>,
+    brush_cache: HashMap<Color, ID2D1SolidColorBrush>,
     visible: bool,
 }
 
 impl Window {
     pub fn new(
-        left: i32,
-        top: i32,
-        right: i32,
-        bottom: i32,
-        draw_rect_list: Arc<RwLock<Vec<RECT>>>,
+        target: OverlayTarget,
+        draw_item_list: Arc<RwLock<Vec<DrawItem>>>,
         draw_bottom_line_flag: bool,
+        shape_window_region: bool,
+        event_sender: Option<Sender<OverlayEvent>>,
+        text_style: TextStyle,
     ) -> Result<Self> {
         let factory = create_factory()?;
         let style = create_style(&factory)?;
+        let dwrite_factory = create_dwrite_factory()?;
+        let text_format = create_text_format(&dwrite_factory, &text_style)?;
 
         Ok(Window {
             handle: Default::default(),
-            window_rect: RECT {
-                left,
-                top,
-                right,
-                bottom,
-            },
-            draw_rect_list,
+            window_rect: target.resolve(),
+            draw_item_list,
             draw_bottom_line_flag,
+            shape_window_region,
+            region_hash: None,
+            event_sender,
             factory,
             style,
+            text_format,
             target: None,
             swapchain: None,
-            brush: None,
+            brush_cache: HashMap::new(),
             visible: false,
         })
     }
@@ -89,6 +175,19 @@ impl Window {
 
             SetLayeredWindowAttributes(handle, COLORREF(0), 0, LWA_COLORKEY)?;
 
+            let _ = RegisterHotKey(
+                Some(handle),
+                HOTKEY_ID_TOGGLE_VISIBILITY,
+                MOD_CONTROL | MOD_ALT,
+                VK_F9.0 as u32,
+            );
+            let _ = RegisterHotKey(
+                Some(handle),
+                HOTKEY_ID_QUIT,
+                MOD_CONTROL | MOD_ALT,
+                VK_F10.0 as u32,
+            );
+
             let mut message = MSG::default();
             loop {
                 if self.visible {
@@ -121,19 +220,22 @@ impl Window {
             let swapchain = create_swapchain(&device, self.handle)?;
             create_swapchain_bitmap(&swapchain, &target)?;
 
-            self.brush = create_brush(&target).ok();
             self.target = Some(target);
             self.swapchain = Some(swapchain);
         }
 
-        let target = self.target.as_ref().unwrap();
+        let target = self.target.as_ref().unwrap().clone();
         unsafe { target.BeginDraw() };
-        self.draw(target)?;
+        self.draw(&target)?;
 
         unsafe {
             target.EndDraw(None, None)?;
         }
 
+        if self.shape_window_region {
+            self.update_window_region();
+        }
+
         if let Err(error) = self.present(1, DXGI_PRESENT(0)) {
             if error.code() == DXGI_STATUS_OCCLUDED {
                 self.visible = false;
@@ -145,6 +247,150 @@ impl Window {
         Ok(())
     }
 
+    fn resize(&mut self, new_width: u32, new_height: u32) -> Result<()> {
+        if let (Some(target), Some(swapchain)) = (self.target.as_ref(), self.swapchain.as_ref()) {
+            unsafe { target.SetTarget(None) };
+
+            unsafe {
+                swapchain.ResizeBuffers(
+                    0,
+                    new_width,
+                    new_height,
+                    DXGI_FORMAT_UNKNOWN,
+                    DXGI_SWAP_CHAIN_FLAG(0),
+                )?;
+            }
+
+            create_swapchain_bitmap(swapchain, target)?;
+        }
+
+        self.window_rect.right = self.window_rect.left + new_width as i32;
+        self.window_rect.bottom = self.window_rect.top + new_height as i32;
+
+        Ok(())
+    }
+
+    /// Shapes the window to just the pixels actually drawn by [`Window::draw`] — the stroked
+    /// bands, each item's label layout rect, and the full interior of `filled` items — so the
+    /// DWM doesn't composite anything else. Re-applying the region is skipped unless the draw
+    /// list has changed since the last call.
+    fn update_window_region(&mut self) {
+        let draw_item_list = {
+            let draw_item_list_lock = self.draw_item_list.read().unwrap();
+            draw_item_list_lock.clone()
+        };
+
+        let hash = hash_draw_item_list(&draw_item_list);
+        if self.region_hash == Some(hash) {
+            return;
+        }
+        self.region_hash = Some(hash);
+
+        let origin_x = self.window_rect.left;
+        let origin_y = self.window_rect.top;
+
+        unsafe {
+            let region = CreateRectRgn(0, 0, 0, 0);
+
+            for item in &draw_item_list {
+                let rect = RECT {
+                    left: item.rect.left - origin_x,
+                    top: item.rect.top - origin_y,
+                    right: item.rect.right - origin_x,
+                    bottom: item.rect.bottom - origin_y,
+                };
+                let rect = &rect;
+                // Direct2D centres each stroke on the outline, so every band straddles its edge.
+                let half = (item.stroke_width.max(STROKE_WIDTH as f32) / 2.0).ceil() as i32;
+
+                let edges = [
+                    RECT {
+                        left: rect.left,
+                        top: rect.top,
+                        right: rect.right,
+                        bottom: rect.top,
+                    },
+                    RECT {
+                        left: rect.left,
+                        top: rect.bottom,
+                        right: rect.right,
+                        bottom: rect.bottom,
+                    },
+                    RECT {
+                        left: rect.left,
+                        top: rect.top,
+                        right: rect.left,
+                        bottom: rect.bottom,
+                    },
+                    RECT {
+                        left: rect.right,
+                        top: rect.top,
+                        right: rect.right,
+                        bottom: rect.bottom,
+                    },
+                ];
+
+                for edge in edges {
+                    let edge_region = CreateRectRgn(
+                        edge.left - half,
+                        edge.top - half,
+                        edge.right + half,
+                        edge.bottom + half,
+                    );
+                    CombineRgn(region, region, edge_region, RGN_OR);
+                    let _ = DeleteObject(edge_region);
+                }
+
+                if item.filled {
+                    let fill_region = CreateRectRgn(rect.left, rect.top, rect.right, rect.bottom);
+                    CombineRgn(region, region, fill_region, RGN_OR);
+                    let _ = DeleteObject(fill_region);
+                }
+
+                if item.label.is_some() {
+                    let label_rect = match item.anchor {
+                        DrawAnchor::TopLeft => RECT {
+                            left: rect.left,
+                            top: rect.top,
+                            right: rect.right,
+                            bottom: rect.top + LABEL_HEIGHT,
+                        },
+                        DrawAnchor::AboveBox => RECT {
+                            left: rect.left,
+                            top: rect.top - LABEL_HEIGHT,
+                            right: rect.right,
+                            bottom: rect.top,
+                        },
+                    };
+                    let label_region = CreateRectRgn(
+                        label_rect.left,
+                        label_rect.top,
+                        label_rect.right,
+                        label_rect.bottom,
+                    );
+                    CombineRgn(region, region, label_region, RGN_OR);
+                    let _ = DeleteObject(label_region);
+                }
+
+                if self.draw_bottom_line_flag {
+                    let rect_width = rect.right - rect.left;
+                    let line_x = rect.left + rect_width / 2;
+                    let center_x = (self.window_rect.right - origin_x) / 2;
+                    let bottom_line_region = CreateRectRgn(
+                        line_x.min(center_x) - half.max(STROKE_WIDTH),
+                        rect.bottom,
+                        line_x.max(center_x) + half.max(STROKE_WIDTH),
+                        self.window_rect.bottom - origin_y,
+                    );
+                    CombineRgn(region, region, bottom_line_region, RGN_OR);
+                    let _ = DeleteObject(bottom_line_region);
+                }
+            }
+
+            let _ = SetWindowRgn(self.handle, region, true);
+        }
+    }
+
     fn release_device(&mut self) {
         self.target = None;
         self.swapchain = None;
@@ -152,14 +398,32 @@ impl Window {
     }
 
     fn release_device_resources(&mut self) {
-        self.brush = None;
+        self.brush_cache.clear();
+    }
+
+    fn get_or_create_brush(
+        &mut self,
+        target: &ID2D1DeviceContext,
+        color: Color,
+    ) -> Result<ID2D1SolidColorBrush> {
+        if let Some(brush) = self.brush_cache.get(&color) {
+            return Ok(brush.clone());
+        }
+
+        if self.brush_cache.len() >= MAX_CACHED_BRUSHES {
+            self.brush_cache.clear();
+        }
+
+        let brush = create_colored_brush(target, color)?;
+        self.brush_cache.insert(color, brush.clone());
+        Ok(brush)
     }
 
     fn present(&self, sync: u32, flags: DXGI_PRESENT) -> Result<()> {
         unsafe { self.swapchain.as_ref().unwrap().Present(sync, flags).ok() }
     }
 
-    fn draw(&self, target: &ID2D1DeviceContext) -> Result<()> {
+    fn draw(&mut self, target: &ID2D1DeviceContext) -> Result<()> {
         unsafe {
             target.Clear(Some(&D2D1_COLOR_F {
                 r: 0.0,
@@ -167,41 +431,89 @@ impl Window {
                 b: 0.0,
                 a: 0.0,
             }));
+        }
 
-            let brush = self.brush.as_ref().unwrap();
-            let draw_rect_list = {
-                let draw_rect_list_lock = self.draw_rect_list.read().unwrap();
-                draw_rect_list_lock.clone()
+        let draw_item_list = {
+            let draw_item_list_lock = self.draw_item_list.read().unwrap();
+            draw_item_list_lock.clone()
+        };
+
+        // Draw items carry screen-space coordinates, but the render target's origin sits at the
+        // window's top-left corner, which may itself be off-screen (e.g. a monitor to the left
+        // of the primary one) or anywhere else in a multi-monitor layout.
+        let origin_x = self.window_rect.left;
+        let origin_y = self.window_rect.top;
+
+        for item in &draw_item_list {
+            let rect = &item.rect;
+            let rect_f = D2D_RECT_F {
+                left: (rect.left - origin_x) as f32,
+                top: (rect.top - origin_y) as f32,
+                right: (rect.right - origin_x) as f32,
+                bottom: (rect.bottom - origin_y) as f32,
             };
-            draw_rect_list.iter().for_each(|rect| {
-                target.DrawRectangle(
-                    &D2D_RECT_F {
-                        left: rect.left as f32,
-                        top: rect.top as f32,
-                        right: rect.right as f32,
-                        bottom: rect.bottom as f32,
-                    },
-                    brush,
-                    2.0,
-                    &self.style,
-                );
-                if self.draw_bottom_line_flag {
-                    let rect_width = rect.right - rect.left;
+            let brush = self.get_or_create_brush(target, item.color)?;
+
+            if item.filled {
+                let fill_color = Color {
+                    a: item.color.a / 4,
+                    ..item.color
+                };
+                let fill_brush = self.get_or_create_brush(target, fill_color)?;
+                unsafe { target.FillRectangle(&rect_f, &fill_brush) };
+            }
+
+            unsafe {
+                target.DrawRectangle(&rect_f, &brush, item.stroke_width, &self.style);
+            }
+
+            if self.draw_bottom_line_flag {
+                let rect_width = rect_f.right - rect_f.left;
+                unsafe {
                     target.DrawLine(
                         D2D_POINT_2F {
-                            x: (self.window_rect.right / 2) as f32,
-                            y: self.window_rect.bottom as f32,
+                            x: (self.window_rect.right - origin_x) as f32 / 2.0,
+                            y: (self.window_rect.bottom - origin_y) as f32,
                         },
                         D2D_POINT_2F {
-                            x: (rect.left + rect_width / 2) as f32,
-                            y: rect.bottom as f32,
+                            x: rect_f.left + rect_width / 2.0,
+                            y: rect_f.bottom,
                         },
-                        brush,
-                        2.0,
+                        &brush,
+                        item.stroke_width,
                         &self.style,
                     );
                 }
-            });
+            }
+
+            if let Some(label) = &item.label {
+                let label_height = LABEL_HEIGHT as f32;
+                let layout_rect = match item.anchor {
+                    DrawAnchor::TopLeft => D2D_RECT_F {
+                        left: rect_f.left,
+                        top: rect_f.top,
+                        right: rect_f.right,
+                        bottom: rect_f.top + label_height,
+                    },
+                    DrawAnchor::AboveBox => D2D_RECT_F {
+                        left: rect_f.left,
+                        top: rect_f.top - label_height,
+                        right: rect_f.right,
+                        bottom: rect_f.top,
+                    },
+                };
+                let label_utf16: Vec<u16> = label.encode_utf16().collect();
+                unsafe {
+                    target.DrawText(
+                        &label_utf16,
+                        &self.text_format,
+                        &layout_rect,
+                        &brush,
+                        D2D1_DRAW_TEXT_OPTIONS_NONE,
+                        DWRITE_MEASURING_MODE_NATURAL,
+                    );
+                }
+            }
         }
 
         Ok(())
@@ -221,6 +533,18 @@ impl Window {
                     self.render().unwrap();
                     LRESULT(0)
                 }
+                WM_SIZE => {
+                    let new_width = (lparam.0 & 0xFFFF) as u32;
+                    let new_height = ((lparam.0 >> 16) & 0xFFFF) as u32;
+                    // SIZE_MINIMIZED reports 0x0; ignore it rather than collapsing window_rect.
+                    if new_width > 0 && new_height > 0 && self.resize(new_width, new_height).is_err()
+                    {
+                        // Mirrors the present() error path: drop the device and let the next
+                        // render() recreate it instead of panicking across the wndproc boundary.
+                        self.release_device();
+                    }
+                    LRESULT(0)
+                }
                 WM_USER => {
                     if self.present(0, DXGI_PRESENT_TEST).is_ok() {
                         self.visible = true;
@@ -231,7 +555,27 @@ impl Window {
                     self.visible = true;
                     LRESULT(0)
                 }
+                WM_HOTKEY => {
+                    match wparam.0 as i32 {
+                        HOTKEY_ID_TOGGLE_VISIBILITY => {
+                            self.visible = !self.visible;
+                            if let Some(sender) = &self.event_sender {
+                                let _ = sender.send(OverlayEvent::VisibilityToggled(self.visible));
+                            }
+                        }
+                        HOTKEY_ID_QUIT => {
+                            if let Some(sender) = &self.event_sender {
+                                let _ = sender.send(OverlayEvent::Quit);
+                            }
+                            PostQuitMessage(0);
+                        }
+                        _ => {}
+                    }
+                    LRESULT(0)
+                }
                 WM_DESTROY => {
+                    let _ = UnregisterHotKey(Some(self.handle), HOTKEY_ID_TOGGLE_VISIBILITY);
+                    let _ = UnregisterHotKey(Some(self.handle), HOTKEY_ID_QUIT);
                     PostQuitMessage(0);
                     LRESULT(0)
                 }
@@ -266,20 +610,40 @@ impl Window {
     }
 }
 
-fn create_brush(target: &ID2D1DeviceContext) -> Result<ID2D1SolidColorBrush> {
-    let color = D2D1_COLOR_F {
-        r: 0.92,
-        g: 0.38,
-        b: 0.208,
-        a: 1.0,
+fn hash_draw_item_list(draw_item_list: &[DrawItem]) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    draw_item_list.len().hash(&mut hasher);
+    for item in draw_item_list {
+        item.rect.left.hash(&mut hasher);
+        item.rect.top.hash(&mut hasher);
+        item.rect.right.hash(&mut hasher);
+        item.rect.bottom.hash(&mut hasher);
+        item.label.hash(&mut hasher);
+        (item.anchor as u8).hash(&mut hasher);
+        item.color.hash(&mut hasher);
+        item.stroke_width.to_bits().hash(&mut hasher);
+        item.filled.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn create_colored_brush(target: &ID2D1DeviceContext, color: Color) -> Result<ID2D1SolidColorBrush> {
+    let d2d_color = D2D1_COLOR_F {
+        r: color.r as f32 / 255.0,
+        g: color.g as f32 / 255.0,
+        b: color.b as f32 / 255.0,
+        a: color.a as f32 / 255.0,
     };
 
     let properties = D2D1_BRUSH_PROPERTIES {
-        opacity: 0.8,
+        opacity: 1.0,
         ..Default::default()
     };
 
-    unsafe { target.CreateSolidColorBrush(&color, Some(&properties)) }
+    unsafe { target.CreateSolidColorBrush(&d2d_color, Some(&properties)) }
 }
 
 fn create_factory() -> Result<ID2D1Factory1> {
@@ -302,6 +666,28 @@ fn create_style(factory: &ID2D1Factory1) -> Result<ID2D1StrokeStyle1> {
     unsafe { factory.CreateStrokeStyle(&props, None) }
 }
 
+fn create_dwrite_factory() -> Result<IDWriteFactory> {
+    unsafe { DWriteCreateFactory(DWRITE_FACTORY_TYPE_SHARED) }
+}
+
+fn create_text_format(
+    dwrite_factory: &IDWriteFactory,
+    text_style: &TextStyle,
+) -> Result<IDWriteTextFormat> {
+    let font_family = HSTRING::from(&text_style.font_family);
+    unsafe {
+        dwrite_factory.CreateTextFormat(
+            &font_family,
+            None,
+            text_style.font_weight,
+            DWRITE_FONT_STYLE_NORMAL,
+            DWRITE_FONT_STRETCH_NORMAL,
+            text_style.font_size,
+            w!("en-us"),
+        )
+    }
+}
+
 fn create_device_with_type(drive_type: D3D_DRIVER_TYPE) -> Result<ID3D11Device> {
     let mut flags = D3D11_CREATE_DEVICE_BGRA_SUPPORT;
 
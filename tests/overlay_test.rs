@@ -1,25 +1,62 @@
 #[test]
 fn overlay_test() {
-    let rect_list = std::sync::Arc::new(std::sync::RwLock::new(vec![
-        windows::Win32::Foundation::RECT {
-            left: 0,
-            top: 0,
-            right: 100,
-            bottom: 100,
+    let item_list = std::sync::Arc::new(std::sync::RwLock::new(vec![
+        windows_ez_overlay::DrawItem {
+            rect: windows::Win32::Foundation::RECT {
+                left: 0,
+                top: 0,
+                right: 100,
+                bottom: 100,
+            },
+            label: Some("player".to_string()),
+            anchor: windows_ez_overlay::DrawAnchor::AboveBox,
+            color: windows_ez_overlay::Color {
+                r: 255,
+                g: 0,
+                b: 0,
+                a: 255,
+            },
+            stroke_width: 2.0,
+            filled: false,
         },
-        windows::Win32::Foundation::RECT {
-            left: 123,
-            top: 456,
-            right: 789,
-            bottom: 666,
+        windows_ez_overlay::DrawItem {
+            rect: windows::Win32::Foundation::RECT {
+                left: 123,
+                top: 456,
+                right: 789,
+                bottom: 666,
+            },
+            label: None,
+            anchor: windows_ez_overlay::DrawAnchor::TopLeft,
+            color: windows_ez_overlay::Color {
+                r: 0,
+                g: 255,
+                b: 0,
+                a: 255,
+            },
+            stroke_width: 3.0,
+            filled: true,
         },
     ]));
 
     {
-        let rect_list = rect_list.clone();
+        let item_list = item_list.clone();
         std::thread::spawn(move || {
-            let mut overlay =
-                windows_ez_overlay::Window::new(0, 0, 1920, 1080, rect_list, true).unwrap();
+            let target = windows_ez_overlay::OverlayTarget::Rect(windows::Win32::Foundation::RECT {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            });
+            let mut overlay = windows_ez_overlay::Window::new(
+                target,
+                item_list,
+                true,
+                true,
+                None,
+                windows_ez_overlay::TextStyle::default(),
+            )
+            .unwrap();
             overlay.run().unwrap();
         });
     }
@@ -31,12 +68,12 @@ fn overlay_test() {
     let mut last_tick = std::time::Instant::now();
     loop {
         {
-            let mut rect_list = rect_list.write().unwrap();
-            rect_list.iter_mut().for_each(|rect| {
-                rect.left += 1;
-                rect.top += 1;
-                rect.right += 1;
-                rect.bottom += 1;
+            let mut item_list = item_list.write().unwrap();
+            item_list.iter_mut().for_each(|item| {
+                item.rect.left += 1;
+                item.rect.top += 1;
+                item.rect.right += 1;
+                item.rect.bottom += 1;
             });
         }
 